@@ -10,18 +10,25 @@ import 'prismjs/themes/prism-tomorrow.css';
 
 // --- DEFINIÇÕES DE TIPOS ---
 type Screen = 'connections' | 'query' | 'execution';
-type SaveOption = 'single' | 'separate' | 'none';
+type SaveOption = 'single' | 'separate' | 'workbook' | 'none';
+type ErrorMode = 'stopBatch' | 'stopDatabase' | 'continue';
 type UtilityPanelTab = 'history' | 'snippets';
 type SuggestionContext = 'schema' | 'table' | 'column' | 'keyword' | null;
 
-interface Connection { id: string; name: string; host: string; port: string; user: string; pass: string; savePass: boolean; }
+interface SshTunnelConfig { host: string; port?: number; user: string; keyPath?: string; trustNewHostKey?: boolean; }
+interface Connection { id: string; name: string; host: string; port: string; user: string; pass: string; savePass: boolean; group?: string; order?: number; readOnly?: boolean; defaultDatabase?: string; notes?: string; sshTunnel?: SshTunnelConfig; }
 type ConnectionFormData = Omit<Connection, 'id'>;
 type ExecutionStatus = 'waiting' | 'success' | 'error';
-interface QueryResult { headers: string[]; rows: string[][]; }
-type ExecutionResult = | { type: 'select'; payload: QueryResult } | { type: 'mutation'; payload: { affectedRows: number } } | { type: 'error'; payload: string };
-interface DatabaseStatus { name: string; status: ExecutionStatus; log?: string; results: ExecutionResult[]; }
-interface DatabaseInfo { name: string; status: number; }
-interface HistoryEntry { id: number; query_text: string; connection_name: string; status: string; timestamp: string; }
+interface QueryResult { headers: string[]; rows: string[][]; truncated?: boolean; statementIndex?: number; resultIndex?: number; columnTypes?: string[]; }
+const NUMERIC_TYPES = ['int2', 'int4', 'int8', 'float4', 'float8', 'numeric', 'money', 'oid'];
+type ExecutionResult = | { type: 'select'; payload: QueryResult } | { type: 'mutation'; payload: { affectedRows: number } } | { type: 'command'; payload: { command: string } } | { type: 'error'; payload: QueryError };
+interface QueryError { message: string; code?: string | null; position?: number | null; line?: number | null; column?: number | null; }
+interface ServerNotice { statementIndex: number; severity: string; message: string; }
+interface DatabaseStatus { name: string; status: ExecutionStatus; log?: string; results: ExecutionResult[]; notices?: ServerNotice[]; }
+interface CommandError { kind: 'connection' | 'syntax' | 'permission' | 'query' | 'validation' | 'internal'; message: string; code?: string | null; }
+interface FlaggedStatement { statementIndex: number; statement: string; reason: string; }
+interface DatabaseInfo { name: string; status: number; size_bytes: number | null; owner: string; }
+interface HistoryEntry { id: number; query_text: string; connection_name: string; status: string; timestamp: string; databases: string[]; affected_rows: number | null; result_rows: number | null; favorite: boolean; }
 interface Snippet { id: number; name: string; description: string; content: string; }
 type SnippetFormData = Omit<Snippet, 'id'>;
 interface ColumnInfo { name: string; data_type: string; }
@@ -54,14 +61,16 @@ const AutocompleteBox = ({ suggestions, onSelect, activeIndex }: { suggestions:
         </div>
     );
 };
+const errorMessage = (err: unknown) => typeof err === 'string' ? err : (err as CommandError).code ? `${(err as CommandError).message} (${(err as CommandError).code})` : (err as CommandError).message;
+const formatBytes = (bytes: number) => { const units = ['B', 'KB', 'MB', 'GB', 'TB']; let value = bytes; let unit = 0; while (value >= 1024 && unit < units.length - 1) { value /= 1024; unit++; } return `${value.toFixed(unit === 0 ? 0 : 1)} ${units[unit]}`; };
 const SyncOverlay = () => ( <div className="sync-overlay"><div className="sync-spinner"></div><span>Sincronizando...</span></div> );
 const SnippetModal = ({ isOpen, onClose, onSave, initialData }: { isOpen: boolean; onClose: () => void; onSave: (data: SnippetFormData) => void; initialData?: Snippet; }) => { const emptyForm: SnippetFormData = { name: '', description: '', content: '' }; const [formData, setFormData] = useState(initialData || emptyForm); const isEditing = !!initialData; useEffect(() => { setFormData(initialData || emptyForm); }, [initialData, isOpen]); if (!isOpen) return null; const handleSave = () => { if (formData.name && formData.content) { onSave(formData); } }; return ( <div className="modal-overlay" onClick={onClose}><div className="modal-content snippet-modal-content" onClick={(e) => e.stopPropagation()}><h2>{isEditing ? 'Editar Snippet' : 'Novo Snippet'}</h2><div className="modal-form"><input type="text" placeholder="Nome do Snippet" value={formData.name} onChange={(e) => setFormData(prev => ({ ...prev, name: e.target.value }))} /><input type="text" placeholder="Descrição (opcional)" value={formData.description} onChange={(e) => setFormData(prev => ({ ...prev, description: e.target.value }))} /><div className="snippet-editor"><Editor value={formData.content} onValueChange={code => setFormData(prev => ({...prev, content: code}))} highlight={code => highlight(code, Prism.languages.sql, 'sql')} padding={10} textareaClassName="search-input" placeholder="Cole seu script SQL aqui... Use {{variavel}} para placeholders." /></div><div className="modal-actions"><button type="button" onClick={onClose} className="action-button">Cancelar</button><button type="button" onClick={handleSave} className="action-button save-button">Salvar</button></div></div></div></div> ); };
 const PlaceholderModal = ({ isOpen, onClose, placeholders, onSubmit }: { isOpen: boolean; onClose: () => void; placeholders: string[]; onSubmit: (values: Record<string, string>) => void; }) => { const [values, setValues] = useState<Record<string, string>>({}); useEffect(() => { if (isOpen) { const initialValues: Record<string, string> = {}; placeholders.forEach(p => { initialValues[p] = ''; }); setValues(initialValues); } }, [isOpen, placeholders]); if (!isOpen) return null; const handleValueChange = (placeholder: string, value: string) => { setValues(prev => ({...prev, [placeholder]: value})); }; const handleSubmit = () => { onSubmit(values); onClose(); }; return ( <div className="modal-overlay" onClick={onClose}><div className="modal-content placeholder-modal-content" onClick={(e) => e.stopPropagation()}><h2>Preencher Variáveis do Snippet</h2><div className="modal-form">{placeholders.map(placeholder => ( <div key={placeholder} className="placeholder-input-group"><label htmlFor={placeholder}>{placeholder}</label><input id={placeholder} type="text" value={values[placeholder] || ''} onChange={(e) => handleValueChange(placeholder, e.target.value)} autoFocus={placeholders[0] === placeholder} /></div> ))}<div className="modal-actions"><button type="button" onClick={onClose} className="action-button">Cancelar</button><button type="button" onClick={handleSubmit} className="action-button save-button">Confirmar</button></div></div></div></div> ); };
-const ExecutionResultModal = ({ isOpen, onClose, results }: { isOpen: boolean; onClose: () => void; results: ExecutionResult[] | null; }) => { const [activeTabIndex, setActiveTabIndex] = useState(0); useEffect(() => { if (isOpen) setActiveTabIndex(0); }, [isOpen, results]); if (!isOpen || !results || results.length === 0) return null; const getTabInfo = (result: ExecutionResult, index: number) => { switch (result.type) { case 'select': return { icon: '📄', label: `SELECT (${result.payload.rows.length} linhas)` }; case 'mutation': return { icon: '✔️', label: `Mutação` }; case 'error': return { icon: '❌', label: `Erro Query ${index + 1}` }; default: return { icon: '❓', label: `Query ${index + 1}` }; } }; const activeResult = results[activeTabIndex]; return ( <div className="modal-overlay" onClick={onClose}><div className="modal-content result-modal-content" onClick={(e) => e.stopPropagation()}><h2>Resultados da Execução</h2><div className="tab-container"><div className="tab-buttons">{results.map((result, index) => { const { icon, label } = getTabInfo(result, index); return (<button key={index} className={`tab-button ${index === activeTabIndex ? 'active' : ''}`} onClick={() => setActiveTabIndex(index)}><span>{icon}</span><span>{label}</span></button>); })}</div><div className="tab-content">{activeResult.type === 'select' ? (<div className="result-table-container">{activeResult.payload.rows.length > 0 ? (<table><thead><tr>{activeResult.payload.headers.map((h, i) => <th key={i}>{h}</th>)}</tr></thead><tbody>{activeResult.payload.rows.map((r, i) => <tr key={i}>{r.map((c, j) => <td key={j}>{c}</td>)}</tr>)}</tbody></table>) : <p><i>Query executada com sucesso, mas não retornou linhas.</i></p>}</div>) : activeResult.type === 'mutation' ? (<p className="mutation-result">✔️ Sucesso! {activeResult.payload.affectedRows} linha(s) afetada(s).</p>) : <p className="error-result">❌ {activeResult.payload}</p>}</div></div><div className="modal-actions"><button type="button" onClick={onClose} className="action-button">Fechar</button></div></div></div> ); };
-const LogModal = ({ isOpen, onClose, logs }: { isOpen: boolean; onClose: () => void; logs: DatabaseStatus[]; }) => { if (!isOpen) return null; return (<div className="modal-overlay" onClick={onClose}><div className="modal-content log-modal-content" onClick={(e) => e.stopPropagation()}><h2>Logs de Erro</h2><div className="log-entries">{logs.map((log, index) => (<div key={index} className="log-entry"><h4>{log.name}</h4><pre>{log.log}</pre></div>))}</div><div className="modal-actions"><button type="button" onClick={onClose} className="action-button">Fechar</button></div></div></div>); };
+const ExecutionResultModal = ({ isOpen, onClose, results }: { isOpen: boolean; onClose: () => void; results: ExecutionResult[] | null; }) => { const [activeTabIndex, setActiveTabIndex] = useState(0); useEffect(() => { if (isOpen) setActiveTabIndex(0); }, [isOpen, results]); if (!isOpen || !results || results.length === 0) return null; const getTabInfo = (result: ExecutionResult, index: number) => { switch (result.type) { case 'select': return { icon: '📄', label: `SELECT ${(result.payload.resultIndex ?? 0) + 1} · Query ${(result.payload.statementIndex ?? index) + 1} (${result.payload.rows.length} linhas)` }; case 'mutation': return { icon: '✔️', label: `Mutação` }; case 'error': return { icon: '❌', label: `Erro Query ${index + 1}` }; default: return { icon: '❓', label: `Query ${index + 1}` }; } }; const activeResult = results[activeTabIndex]; return ( <div className="modal-overlay" onClick={onClose}><div className="modal-content result-modal-content" onClick={(e) => e.stopPropagation()}><h2>Resultados da Execução</h2><div className="tab-container"><div className="tab-buttons">{results.map((result, index) => { const { icon, label } = getTabInfo(result, index); return (<button key={index} className={`tab-button ${index === activeTabIndex ? 'active' : ''}`} onClick={() => setActiveTabIndex(index)}><span>{icon}</span><span>{label}</span></button>); })}</div><div className="tab-content">{activeResult.type === 'select' ? (<div className="result-table-container">{activeResult.payload.rows.length > 0 ? (<table><thead><tr>{activeResult.payload.headers.map((h, i) => <th key={i}>{h}</th>)}</tr></thead><tbody>{activeResult.payload.rows.map((r, i) => <tr key={i}>{r.map((c, j) => <td key={j} style={NUMERIC_TYPES.includes(activeResult.payload.columnTypes?.[j] ?? '') ? { textAlign: 'right' } : undefined}>{c}</td>)}</tr>)}</tbody></table>) : <p><i>Query executada com sucesso, mas não retornou linhas.</i></p>}</div>) : activeResult.type === 'mutation' ? (<p className="mutation-result">✔️ Sucesso! {activeResult.payload.affectedRows} linha(s) afetada(s).</p>) : activeResult.type === 'command' ? (<p className="mutation-result">✔️ {activeResult.payload.command} executado com sucesso.</p>) : <p className="error-result">❌ {activeResult.payload.message}{activeResult.payload.line ? ` (linha ${activeResult.payload.line}, coluna ${activeResult.payload.column})` : ''}</p>}</div></div><div className="modal-actions"><button type="button" onClick={onClose} className="action-button">Fechar</button></div></div></div> ); };
+const LogModal = ({ isOpen, onClose, logs }: { isOpen: boolean; onClose: () => void; logs: DatabaseStatus[]; }) => { if (!isOpen) return null; return (<div className="modal-overlay" onClick={onClose}><div className="modal-content log-modal-content" onClick={(e) => e.stopPropagation()}><h2>Logs</h2><div className="log-entries">{logs.map((log, index) => (<div key={index} className="log-entry"><h4>{log.name}</h4><pre>{log.log}</pre></div>))}</div><div className="modal-actions"><button type="button" onClick={onClose} className="action-button">Fechar</button></div></div></div>); };
 const ReturnOptionsModal = ({ isOpen, onClose, onSelectPrevious, onSelectErrors }: { isOpen: boolean; onClose: () => void; onSelectPrevious: () => void; onSelectErrors: () => void; }) => { if (!isOpen) return null; return (<div className="modal-overlay" onClick={onClose}><div className="modal-content" onClick={(e) => e.stopPropagation()}><h2>Opções de Retorno</h2><p>Selecione qual tipo de seleção você deseja manter ao retornar para a página anterior.</p><div className="modal-actions return-options"><button type="button" onClick={onSelectPrevious} className="action-button">Seleção Anterior</button><button type="button" onClick={onSelectErrors} className="action-button">Somente Erros</button><button type="button" onClick={onClose} className="action-button">Cancelar</button></div></div></div>); };
-const SaveOptionsModal = ({ isOpen, onClose, onSelect }: { isOpen: boolean; onClose: () => void; onSelect: (option: SaveOption) => void; }) => { if (!isOpen) return null; return (<div className="modal-overlay" onClick={onClose}><div className="modal-content" onClick={(e) => e.stopPropagation()}><h2>Salvar Resultados</h2><p>Como você deseja salvar os resultados da query?</p><div className="modal-actions return-options"><button type="button" onClick={() => onSelect('single')} className="action-button">Arquivo Único</button><button type="button" onClick={() => onSelect('separate')} className="action-button">Arquivos Separados</button><button type="button" onClick={() => onSelect('none')} className="action-button">Não Salvar</button><button type="button" onClick={onClose} className="action-button">Cancelar</button></div></div></div>); };
-const ConnectionModal = ({ mode, isOpen, onClose, onSave, initialValues }: { mode: 'new' | 'edit'; isOpen: boolean; onClose: () => void; onSave: (data: ConnectionFormData) => void; initialValues?: ConnectionFormData; }) => { const emptyForm: ConnectionFormData = { name: '', host: '', port: '', user: '', pass: '', savePass: false }; const [formData, setFormData] = useState(initialValues || emptyForm); useEffect(() => { if (isOpen) { setFormData(initialValues || emptyForm); } }, [isOpen, initialValues]); if (!isOpen) { return null; } const handleSubmit = (e: React.FormEvent) => { e.preventDefault(); if (formData.name.trim()) { onSave(formData); } }; const handleChange = (e: React.ChangeEvent<HTMLInputElement>) => { const { name, value, type, checked } = e.target; setFormData(prev => ({ ...prev, [name]: type === 'checkbox' ? checked : value, })); }; return ( <div className="modal-overlay" onClick={onClose}><div className="modal-content" onClick={(e) => e.stopPropagation()}><h2>{mode === 'new' ? 'Nova Conexão' : 'Editar Conexão'}</h2><form onSubmit={handleSubmit} className="modal-form"><input type="text" name="name" value={formData.name} onChange={handleChange} placeholder="Nome da Conexão" autoFocus /><input type="text" name="host" value={formData.host} onChange={handleChange} placeholder="IP do Servidor" /><input type="text" name="port" value={formData.port} onChange={handleChange} placeholder="Porta do Servidor" /><input type="text" name="user" value={formData.user} onChange={handleChange} placeholder="Usuário" /><input type="password" name="pass" value={formData.pass} onChange={handleChange} placeholder="Senha" /><div><label className="checkbox-label"><input type="checkbox" name="savePass" checked={formData.savePass} onChange={handleChange} /> Salvar Senha</label></div><div className="modal-actions"><button type="button" onClick={onClose} className="action-button">Cancelar</button><button type="submit" className="action-button save-button">Salvar</button></div></form></div></div> ); };
+const SaveOptionsModal = ({ isOpen, onClose, onSelect }: { isOpen: boolean; onClose: () => void; onSelect: (option: SaveOption) => void; }) => { if (!isOpen) return null; return (<div className="modal-overlay" onClick={onClose}><div className="modal-content" onClick={(e) => e.stopPropagation()}><h2>Salvar Resultados</h2><p>Como você deseja salvar os resultados da query?</p><div className="modal-actions return-options"><button type="button" onClick={() => onSelect('single')} className="action-button">Arquivo Único</button><button type="button" onClick={() => onSelect('separate')} className="action-button">Arquivos Separados</button><button type="button" onClick={() => onSelect('workbook')} className="action-button">Excel (Uma Aba por Banco)</button><button type="button" onClick={() => onSelect('none')} className="action-button">Não Salvar</button><button type="button" onClick={onClose} className="action-button">Cancelar</button></div></div></div>); };
+const ConnectionModal = ({ mode, isOpen, onClose, onSave, initialValues }: { mode: 'new' | 'edit'; isOpen: boolean; onClose: () => void; onSave: (data: ConnectionFormData) => void; initialValues?: ConnectionFormData; }) => { const emptyForm: ConnectionFormData = { name: '', host: '', port: '', user: '', pass: '', savePass: false }; const [formData, setFormData] = useState(initialValues || emptyForm); useEffect(() => { if (isOpen) { setFormData(initialValues || emptyForm); } }, [isOpen, initialValues]); if (!isOpen) { return null; } const handleSubmit = (e: React.FormEvent) => { e.preventDefault(); if (formData.name.trim()) { onSave(formData); } }; const handleChange = (e: React.ChangeEvent<HTMLInputElement>) => { const { name, value, type, checked } = e.target; setFormData(prev => ({ ...prev, [name]: type === 'checkbox' ? checked : value, })); }; const handleSshChange = (e: React.ChangeEvent<HTMLInputElement>) => { const { name, value, type, checked } = e.target; const fieldValue = type === 'checkbox' ? checked : name === 'port' ? (parseInt(value, 10) || undefined) : value; setFormData(prev => ({ ...prev, sshTunnel: { host: '', user: '', ...prev.sshTunnel, [name]: fieldValue } })); }; return ( <div className="modal-overlay" onClick={onClose}><div className="modal-content" onClick={(e) => e.stopPropagation()}><h2>{mode === 'new' ? 'Nova Conexão' : 'Editar Conexão'}</h2><form onSubmit={handleSubmit} className="modal-form"><input type="text" name="name" value={formData.name} onChange={handleChange} placeholder="Nome da Conexão" autoFocus /><input type="text" name="host" value={formData.host} onChange={handleChange} placeholder="IP do Servidor" /><input type="text" name="port" value={formData.port} onChange={handleChange} placeholder="Porta do Servidor" /><input type="text" name="user" value={formData.user} onChange={handleChange} placeholder="Usuário" /><input type="text" name="defaultDatabase" value={formData.defaultDatabase ?? ''} onChange={handleChange} placeholder="Banco padrão (opcional)" /><input type="text" name="notes" value={formData.notes ?? ''} onChange={handleChange} placeholder="Observações (ex.: produção, não rodar DDL)" /><input type="password" name="pass" value={formData.pass} onChange={handleChange} placeholder="Senha" /><div><label className="checkbox-label"><input type="checkbox" name="savePass" checked={formData.savePass} onChange={handleChange} /> Salvar Senha</label></div><div><label className="checkbox-label"><input type="checkbox" name="readOnly" checked={formData.readOnly ?? false} onChange={handleChange} /> Somente leitura</label></div><fieldset className="ssh-tunnel-fieldset"><legend>Túnel SSH (opcional)</legend><input type="text" name="host" value={formData.sshTunnel?.host ?? ''} onChange={handleSshChange} placeholder="Host SSH" /><input type="text" name="port" value={formData.sshTunnel?.port ?? ''} onChange={handleSshChange} placeholder="Porta SSH (22)" /><input type="text" name="user" value={formData.sshTunnel?.user ?? ''} onChange={handleSshChange} placeholder="Usuário SSH" /><input type="text" name="keyPath" value={formData.sshTunnel?.keyPath ?? ''} onChange={handleSshChange} placeholder="Caminho da chave privada (ou ssh-agent)" /><p className="ssh-tunnel-note">Só autenticação por chave ou ssh-agent: o túnel não consegue pedir senha.</p><label className="checkbox-label"><input type="checkbox" name="trustNewHostKey" checked={formData.sshTunnel?.trustNewHostKey ?? false} onChange={handleSshChange} /> Confiar na chave do host na primeira conexão</label></fieldset><div className="modal-actions"><button type="button" onClick={onClose} className="action-button">Cancelar</button><button type="submit" className="action-button save-button">Salvar</button></div></form></div></div> ); };
 const ConfirmDeleteModal = ({ isOpen, onClose, onConfirm }: { isOpen: boolean; onClose: () => void; onConfirm: () => void; }) => { if (!isOpen) { return null; } return (<div className="modal-overlay" onClick={onClose}><div className="modal-content" onClick={(e) => e.stopPropagation()}><h2>Confirmar Exclusão</h2><p>Tem certeza que deseja excluir esta conexão?</p><div className="modal-actions"><button type="button" onClick={onClose} className="action-button">Cancelar</button><button type="button" onClick={onConfirm} className="action-button delete-button">Excluir</button></div></div></div>); };
 const UtilityPanel = ({ onSelectQuery, active }: { onSelectQuery: (query: string) => void; active: boolean }) => { const [isExpanded, setIsExpanded] = useState(false); const [activeTab, setActiveTab] = useState<UtilityPanelTab>('history'); const [history, setHistory] = useState<HistoryEntry[]>([]); const [snippets, setSnippets] = useState<Snippet[]>([]); const [isSnippetModalOpen, setIsSnippetModalOpen] = useState(false); const [editingSnippet, setEditingSnippet] = useState<Snippet | undefined>(undefined); const [isPlaceholderModalOpen, setIsPlaceholderModalOpen] = useState(false); const [placeholdersToFill, setPlaceholdersToFill] = useState<string[]>([]); const [snippetToInterpolate, setSnippetToInterpolate] = useState<Snippet | null>(null); const { showNotification } = useNotification(); const fetchHistory = () => invoke<HistoryEntry[]>('get_query_history').then(setHistory).catch(console.error); const fetchSnippets = () => invoke<Snippet[]>('get_snippets').then(setSnippets).catch(console.error); useEffect(() => { if (active && isExpanded) { if (activeTab === 'history') fetchHistory(); else fetchSnippets(); } }, [active, isExpanded, activeTab]); const handleClearHistory = (e: React.MouseEvent) => { e.stopPropagation(); invoke('clear_query_history').then(() => { setHistory([]); showNotification("Histórico limpo com sucesso!"); }).catch(err => showNotification(`Erro ao limpar histórico: ${err}`)); }; const handleSaveSnippet = (data: SnippetFormData) => { const promise = editingSnippet ? invoke('update_snippet', { id: editingSnippet.id, payload: data }) : invoke('create_snippet', { payload: data }); promise.then(() => { showNotification(editingSnippet ? "Snippet atualizado!" : "Snippet salvo!"); fetchSnippets(); }).catch(err => showNotification(`Erro: ${err}`)); setIsSnippetModalOpen(false); setEditingSnippet(undefined); }; const handleDeleteSnippet = (id: number) => { invoke('delete_snippet', { id }) .then(() => { showNotification("Snippet excluído!"); fetchSnippets(); }) .catch(err => showNotification(`Erro ao excluir snippet: ${err}`)); }; const handleUseSnippet = (snippet: Snippet) => { const regex = /{{(.*?)}}/g; const matches = [...snippet.content.matchAll(regex)]; const uniquePlaceholders = [...new Set(matches.map(match => match[1]))]; if (uniquePlaceholders.length > 0) { setPlaceholdersToFill(uniquePlaceholders); setSnippetToInterpolate(snippet); setIsPlaceholderModalOpen(true); } else { onSelectQuery(snippet.content); } }; const handleSubmitPlaceholders = (values: Record<string, string>) => { if (!snippetToInterpolate) return; let interpolatedQuery = snippetToInterpolate.content; for (const key in values) { const value = values[key]; const placeholderRegex = new RegExp(`{{${key}}}`, 'g'); interpolatedQuery = interpolatedQuery.replace(placeholderRegex, value); } onSelectQuery(interpolatedQuery); setSnippetToInterpolate(null); }; return ( <> <SnippetModal isOpen={isSnippetModalOpen} onClose={() => setIsSnippetModalOpen(false)} onSave={handleSaveSnippet} initialData={editingSnippet} /> <PlaceholderModal isOpen={isPlaceholderModalOpen} onClose={() => setIsPlaceholderModalOpen(false)} placeholders={placeholdersToFill} onSubmit={handleSubmitPlaceholders} /> <div className={`utility-panel ${isExpanded ? 'expanded' : ''}`}> <div className="utility-header" onClick={() => setIsExpanded(!isExpanded)}> <div className="utility-tabs"> <button className={`utility-tab-button ${activeTab === 'history' ? 'active' : ''}`} onClick={(e) => { e.stopPropagation(); setActiveTab('history'); }}>Histórico</button> <button className={`utility-tab-button ${activeTab === 'snippets' ? 'active' : ''}`} onClick={(e) => { e.stopPropagation(); setActiveTab('snippets'); }}>Snippets</button> </div> <div className="utility-header-actions"> {activeTab === 'history' && ( <button onClick={handleClearHistory} className="action-button delete-button header-action-button" title="Limpar Histórico">🗑️</button> )} <span className="collapse-icon">{isExpanded ? '▲' : '▼'}</span> </div> </div> {isExpanded && ( <div className="utility-content"> {activeTab === 'history' && ( <div className="history-list"> {history.length > 0 ? ( <ul>{history.map(entry => ( <li key={entry.id} onClick={() => onSelectQuery(entry.query_text)}> <pre className="language-sql" dangerouslySetInnerHTML={{ __html: highlight(entry.query_text, Prism.languages.sql, 'sql')}}/> <span className="history-query-details">{entry.connection_name} - {new Date(entry.timestamp).toLocaleString()}</span> </li>))}</ul> ) : <p className="empty-message">Nenhuma query no histórico.</p>} </div> )} {activeTab === 'snippets' && ( <div className="snippets-list"> <button onClick={() => { setEditingSnippet(undefined); setIsSnippetModalOpen(true); }} className="action-button new-snippet-button">Novo Snippet</button> {snippets.length > 0 ? ( <ul>{snippets.map(snippet => ( <li key={snippet.id}> <div className="snippet-info"> <strong>{snippet.name}</strong> <p>{snippet.description}</p> </div> <div className="snippet-actions"> <button className="action-button" onClick={() => handleUseSnippet(snippet)}>Usar</button> <button className="action-button" onClick={() => { setEditingSnippet(snippet); setIsSnippetModalOpen(true); }}>Editar</button> <button className="action-button delete-button" onClick={() => handleDeleteSnippet(snippet.id)}>Excluir</button> </div> </li>))}</ul> ) : <p className="empty-message">Nenhum snippet salvo.</p>} </div> )} </div> )} </div> </> ); };
 
@@ -84,12 +93,12 @@ const QueryScreen = ({
   schemaInfo,
 }: {
   connection: Connection;
-  databases: { id: string; name: string; checked: boolean; status: number }[];
-  setDatabases: React.Dispatch<React.SetStateAction<{ id: string; name: string; checked: boolean; status: number }[]>>;
+  databases: { id: string; name: string; checked: boolean; status: number; sizeBytes?: number | null; owner?: string }[];
+  setDatabases: React.Dispatch<React.SetStateAction<{ id: string; name: string; checked: boolean; status: number; sizeBytes?: number | null; owner?: string }[]>>;
   isLoading: boolean;
   error: string | null;
   onBack: () => void;
-  onExecute: (query: string, databases: string[], saveOption: SaveOption, stopOnError: boolean) => void;
+  onExecute: (query: string, databases: string[], saveOption: SaveOption, errorMode: ErrorMode) => void;
   query: string;
   setQuery: (query: string) => void;
   autocompleteSourceDb: string | null;
@@ -103,7 +112,7 @@ const QueryScreen = ({
   const [searchTerm, setSearchTerm] = useState('');
   const [isAllSelected, setIsAllSelected] = useState(true);
   const [isSaveOptionsModalOpen, setIsSaveOptionsModalOpen] = useState(false);
-  const [stopOnErrorFlag, setStopOnErrorFlag] = useState(false);
+  const [errorMode, setErrorMode] = useState<ErrorMode>('continue');
   const [suggestions, setSuggestions] = useState<string[]>([]);
   const [activeSuggestionIndex, setActiveSuggestionIndex] = useState(0);
   const [suggestionContext, setSuggestionContext] = useState<SuggestionContext>(null);
@@ -125,10 +134,10 @@ const QueryScreen = ({
     }
   };
 
-  const handleExecuteClick = (stopOnError: boolean) => { setStopOnErrorFlag(stopOnError); setIsSaveOptionsModalOpen(true); };
+  const handleExecuteClick = (mode: ErrorMode) => { setErrorMode(mode); setIsSaveOptionsModalOpen(true); };
   const handleSaveOptionSelect = (saveOption: SaveOption) => {
     const selectedDbs = databases.filter(db => db.checked).map(db => db.name);
-    onExecute(query, selectedDbs, saveOption, stopOnErrorFlag);
+    onExecute(query, selectedDbs, saveOption, errorMode);
     setIsSaveOptionsModalOpen(false);
   };
   const filteredDatabases = databases.filter(db => db.name.toLowerCase().includes(searchTerm.toLowerCase()));
@@ -264,15 +273,17 @@ const QueryScreen = ({
                 const isIndexed = indexedDatabases.includes(db.name);
                 const itemClass = isAutocompleteEnabled ? (isAutocompleteSource ? 'autocomplete-source' : isIndexed ? 'indexed' : 'not-indexed') : 'indexed';
                 return (
-                    <label key={db.id} className={`checkbox-label db-list-item ${itemClass}`} onClick={(e) => handleDbItemClick(e, db)}>
+                    <label key={db.id} className={`checkbox-label db-list-item ${itemClass}`} onClick={(e) => handleDbItemClick(e, db)} title={db.owner ? `Owner: ${db.owner}` : undefined}>
                       <input type="checkbox" checked={db.checked} readOnly />
                       {db.name}
+                      {db.sizeBytes != null && <span className="db-size">{formatBytes(db.sizeBytes)}</span>}
                     </label>
                 );
               })}
             </div>
           </div>
           <div className="query-editor">
+            {connection.notes?.trim() && <div className="connection-notes-banner">⚠️ {connection.notes}</div>}
             <h3>Query</h3>
             <div className="editor-wrapper">
                 <Editor value={query} onValueChange={handleQueryChange} onKeyDown={handleKeyDown} highlight={(code) => highlight(code, Prism.languages.sql, 'sql')} padding={12} textareaId="query-editor" textareaClassName="search-input" placeholder="Insira sua Query aqui" />
@@ -281,8 +292,9 @@ const QueryScreen = ({
           </div>
           <div className="screen-actions">
             <button onClick={onBack} className="action-button">Voltar</button>
-            <button onClick={() => handleExecuteClick(true)} className="action-button warning-button">Executar Sequencial</button>
-            <button onClick={() => handleExecuteClick(false)} className="action-button save-button">Executar</button>
+            <button onClick={() => handleExecuteClick('stopBatch')} className="action-button warning-button">Executar Sequencial</button>
+            <button onClick={() => handleExecuteClick('stopDatabase')} className="action-button warning-button" title="Para no primeiro erro de cada banco, sem interromper os demais">Parar por Banco</button>
+            <button onClick={() => handleExecuteClick('continue')} className="action-button save-button">Executar</button>
           </div>
           <UtilityPanel onSelectQuery={setQuery} active={true} />
         </>
@@ -298,7 +310,7 @@ const ExecutionScreen = ({ databases, onBack, }: { databases: string[]; onBack:
     useEffect(() => { setResults(databases.map(name => ({ name, status: 'waiting' as ExecutionStatus, log: undefined, results: [] }))); const unlistenPromise = listen<DatabaseStatus>('execution-status-update', (event) => { setResults(prevResults => prevResults.map(res => res.name === event.payload.name ? event.payload : res)); }); return () => { unlistenPromise.then(fn => fn()); }; }, [databases]);
     const getStatusIcon = (status: ExecutionStatus) => { if (status === 'waiting') return <span className="status-icon waiting">🟡</span>; if (status === 'success') return <span className="status-icon success">✔️</span>; if (status === 'error') return <span className="status-icon error">❌</span>; return null; };
     const handleViewResult = (results: ExecutionResult[] | undefined) => { if (results && results.length > 0) { setSelectedResults(results); setIsResultModalOpen(true); } };
-    const errorLogs = results.filter(r => r.status === 'error' && r.log);
+    const errorLogs = results.filter(r => r.log && (r.status === 'error' || (r.notices?.length ?? 0) > 0));
     return (
         <div className="execution-screen-container"><LogModal isOpen={isLogModalOpen} onClose={() => setIsLogModalOpen(false)} logs={errorLogs} /><ExecutionResultModal isOpen={isResultModalOpen} onClose={() => setIsResultModalOpen(false)} results={selectedResults} /><div className="execution-list"><ul>{results.map(result => (<li key={result.name}><span>{result.name}</span><div className="status-container">{result.results && result.results.length > 0 && <button className="view-result-button" onClick={() => handleViewResult(result.results)}>👁️</button>}{getStatusIcon(result.status)}</div></li>))}</ul></div><div className="screen-actions"><button onClick={() => setIsLogModalOpen(true)} className="action-button" disabled={errorLogs.length === 0}>Logs</button><button onClick={() => onBack(results)} className="action-button">Voltar</button></div></div>
     );
@@ -310,7 +322,7 @@ function App() {
   const { notification, showNotification } = useNotification();
   const [screen, setScreen] = useState<Screen>('connections');
   const [connections, setConnections] = useState<Connection[]>([]);
-  const [databases, setDatabases] = useState<{ id: string; name: string; checked: boolean; status: number }[]>([]);
+  const [databases, setDatabases] = useState<{ id: string; name: string; checked: boolean; status: number; sizeBytes?: number | null; owner?: string }[]>([]);
   const [isLoadingDatabases, setIsLoadingDatabases] = useState(true);
   const [dbError, setDbError] = useState<string | null>(null);
   const [selectedConnectionId, setSelectedConnectionId] = useState<string | null>(null);
@@ -340,7 +352,7 @@ function App() {
   useEffect(() => {
     if (!isLoaded) return;
     invoke('save_connections', { connections })
-      .catch(err => { showNotification("Erro ao salvar conexões."); });
+      .catch(err => { showNotification(`Erro ao salvar conexões: ${errorMessage(err)}`); });
   }, [connections, isLoaded, showNotification]);
 
   useEffect(() => {
@@ -368,10 +380,10 @@ function App() {
         setDbError(null);
         invoke<DatabaseInfo[]>('get_databases', { connection: selectedConnection })
           .then(dbInfos => {
-            const formattedDbs = dbInfos.map((db, index) => ({ id: `db-${index}`, name: db.name, checked: true, status: db.status, })).sort((a, b) => a.name.localeCompare(b.name));
+            const formattedDbs = dbInfos.map((db, index) => ({ id: `db-${index}`, name: db.name, checked: true, status: db.status, sizeBytes: db.size_bytes, owner: db.owner, })).sort((a, b) => a.name.localeCompare(b.name));
             setDatabases(formattedDbs);
           })
-          .catch(err => { setDbError(err as string); })
+          .catch(err => { setDbError(errorMessage(err)); })
           .finally(() => { setIsLoadingDatabases(false); });
       }
     }
@@ -397,7 +409,7 @@ function App() {
             localStorage.setItem(`autocompleteSource_${selectedConnection.id}`, dbName);
             fetchIndexedDatabases();
         })
-        .catch(err => showNotification(`Erro ao sincronizar: ${err}`))
+        .catch(err => showNotification(`Erro ao sincronizar: ${errorMessage(err)}`))
         .finally(() => setIsSyncingSchema(false));
   };
 
@@ -411,13 +423,15 @@ function App() {
   const handleCloseConfirmDelete = () => { setIsConfirmDeleteOpen(false); };
   const handleBackToConnections = () => { setScreen('connections'); setDatabases([]); setAutocompleteSourceDb(null); setIndexedDatabases([]); };
 
-  const handleExecute = (query: string, databases: string[], saveOption: SaveOption, stopOnError: boolean) => {
+  const handleExecute = async (query: string, databases: string[], saveOption: SaveOption, errorMode: ErrorMode) => {
     if (!selectedConnection || databases.length === 0 || !query.trim()) {
       showNotification("Erro: Verifique a conexão, bancos de dados e a query.");
       return;
     }
-    invoke('add_query_to_history', { queryText: query, connectionName: selectedConnection.name, status: 'executed', }).catch(console.error);
-    invoke('execute_query_on_databases', { connection: selectedConnection, databases, query, saveOption, stopOnError }).catch(err => { showNotification(`Erro ao iniciar execução: ${err}`); });
+    const flagged = await invoke<FlaggedStatement[]>('check_destructive_query', { query }).catch(() => [] as FlaggedStatement[]);
+    if (flagged.length > 0 && !window.confirm(`A query contém comandos destrutivos:\n${flagged.map(f => `• ${f.reason} (comando ${f.statementIndex + 1})`).join('\n')}\n\nExecutar mesmo assim em ${databases.length} banco(s)?`)) return;
+    const historyId = await invoke<number>('add_query_to_history', { queryText: query, connectionName: selectedConnection.name, status: 'executed', databases, }).catch(err => { console.error(err); return undefined; });
+    invoke('execute_query_on_databases', { connection: selectedConnection, databases, query, saveOption, errorMode, options: { confirmDestructive: true, historyId } }).catch(err => { showNotification(`Erro ao iniciar execução: ${errorMessage(err)}`); });
     setExecutionData({ query, databases });
     setScreen('execution');
   };